@@ -1,17 +1,31 @@
-use std::{env::var, path::PathBuf};
+use std::{
+    env::var,
+    ffi::OsStr,
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
 
 use bindgen::{Builder, CargoCallbacks};
-use pkg_config::probe_library;
 
 fn main() {
     let out_path =
         PathBuf::from(var("OUT_DIR").expect("failed to get OUT_DIR environment variable"));
-    let library = probe_library("opusfile").expect("failed to probe library");
+
+    // With the `bundled` feature we compile libopusfile and its libogg/libopus
+    // dependencies from vendored sources, so downstream users need no system
+    // install. Otherwise we fall back to probing an installed opusfile.
+    let include_paths = if var("CARGO_FEATURE_BUNDLED").is_ok() {
+        build_bundled()
+    } else {
+        pkg_config::probe_library("opusfile")
+            .expect("failed to probe library")
+            .include_paths
+    };
+
     let bindings = Builder::default()
         .header("wrapper.h")
         .clang_args(
-            library
-                .include_paths
+            include_paths
                 .iter()
                 .map(|include_path| format!("-I{}", include_path.display())),
         )
@@ -24,3 +38,82 @@ fn main() {
         .write_to_file(out_path.join("bindings.rs"))
         .expect("Failed to write bindings");
 }
+
+/// Compile the vendored libogg, libopus, and libopusfile sources into static
+/// libraries and return the include directories for bindgen. `http.c` is
+/// compiled with `OP_ENABLE_HTTP` left undefined, so its no-HTTP stubs for
+/// `op_open_url`/`op_test_url` remain present but no TLS stack is required.
+fn build_bundled() -> Vec<PathBuf> {
+    let vendor = PathBuf::from("vendor");
+    let ogg = vendor.join("ogg");
+    let opus = vendor.join("opus");
+    let opusfile = vendor.join("opusfile");
+
+    cc::Build::new()
+        .include(ogg.join("include"))
+        .files(c_sources(&ogg.join("src")))
+        .compile("ogg");
+
+    // libopus's src/ also holds the noinst demo/compare programs, each with its
+    // own main(); keep them out of the static library.
+    const OPUS_SRC_NON_LIBRARY: &[&str] =
+        &["opus_demo.c", "repacketizer_demo.c", "opus_compare.c"];
+    cc::Build::new()
+        .include(opus.join("include"))
+        .include(opus.join("celt"))
+        .include(opus.join("silk"))
+        .define("OPUS_BUILD", None)
+        .define("USE_ALLOCA", None)
+        .files(c_sources_except(&opus.join("src"), OPUS_SRC_NON_LIBRARY))
+        .files(c_sources(&opus.join("celt")))
+        .files(c_sources(&opus.join("silk")))
+        .files(c_sources(&opus.join("silk").join("float")))
+        .compile("opus");
+
+    // Enumerate the opusfile library sources explicitly, mirroring its
+    // Makefile.am. http.c is compiled with OP_ENABLE_HTTP left undefined, so
+    // its no-HTTP stubs for op_open_url/op_test_url resolve without a TLS stack.
+    const OPUSFILE_SRC: &[&str] = &[
+        "info.c",
+        "internal.c",
+        "opusfile.c",
+        "stream.c",
+        "http.c",
+        "wincerts.c",
+    ];
+    cc::Build::new()
+        .include(opusfile.join("include"))
+        .include(ogg.join("include"))
+        .include(opus.join("include"))
+        .files(OPUSFILE_SRC.iter().map(|name| opusfile.join("src").join(name)))
+        .compile("opusfile");
+
+    // `cc` already emits the `cargo:rustc-link-lib=static=<name>` lines for the
+    // libraries it compiles, in reverse dependency order.
+    vec![
+        opusfile.join("include"),
+        opus.join("include"),
+        ogg.join("include"),
+    ]
+}
+
+/// Collect every `*.c` file directly inside `dir`.
+fn c_sources(dir: &Path) -> Vec<PathBuf> {
+    c_sources_except(dir, &[])
+}
+
+/// Collect every `*.c` file directly inside `dir`, skipping the named files.
+fn c_sources_except(dir: &Path, exclude: &[&str]) -> Vec<PathBuf> {
+    read_dir(dir)
+        .unwrap_or_else(|error| panic!("failed to read {}: {error}", dir.display()))
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(OsStr::to_str) == Some("c"))
+        .filter(|path| {
+            !path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| exclude.contains(&name))
+        })
+        .collect()
+}
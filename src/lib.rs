@@ -1,13 +1,25 @@
 mod bindings;
 
-use std::{ffi::CString, path::Path, ptr::null_mut};
+use std::{
+    ffi::{c_char, c_int, c_uchar, c_void, CStr, CString},
+    io::{Read, Seek, SeekFrom},
+    marker::PhantomData,
+    mem::zeroed,
+    path::Path,
+    ptr::{null, null_mut},
+    slice,
+    str::from_utf8,
+};
 
 use bindings::{
     op_bitrate, op_bitrate_instant, op_channel_count, op_current_link, op_free, op_head,
-    op_link_count, op_open_file, op_open_memory, op_open_url, op_pcm_seek, op_pcm_tell,
-    op_pcm_total, op_raw_seek, op_raw_tell, op_raw_total, op_read, op_read_float,
+    op_link_count, op_open_callbacks, op_open_file, op_open_memory, op_open_url, op_pcm_seek,
+    op_pcm_tell, op_pcm_total, op_raw_seek, op_raw_tell, op_raw_total, op_read, op_read_float,
     op_read_float_stereo, op_read_stereo, op_seekable, op_serialno, op_tags, op_test_file,
-    op_test_memory, op_test_open, op_test_url,
+    op_set_gain_offset_q8, op_test_memory, op_test_open, op_test_url, opus_picture_tag_clear,
+    opus_picture_tag_init,
+    opus_picture_tag_parse, opus_tags_clear, opus_tags_get_album_gain, opus_tags_get_track_gain,
+    opus_tags_parse, opus_tags_query, opus_tags_query_count,
 };
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
@@ -53,8 +65,40 @@ pub enum OpusFileError {
     BadTimestamp = bindings::OP_EBADTIMESTAMP,
 }
 
-#[derive(Clone, Debug)]
-pub struct OggOpusFile(*mut bindings::OggOpusFile);
+/// The gain to apply on top of each decoded sample, selected by [`OggOpusFile::set_gain_offset`].
+#[derive(Clone, Copy, Debug)]
+pub enum GainType {
+    /// The header output gain plus the offset.
+    Header,
+    /// The header output gain, the `R128_ALBUM_GAIN` tag, and the offset.
+    Album,
+    /// The header output gain, the `R128_TRACK_GAIN` tag, and the offset.
+    Track,
+    /// The offset only; the header output gain is disabled.
+    Absolute,
+}
+
+impl GainType {
+    fn as_raw(self) -> c_int {
+        match self {
+            GainType::Header => bindings::OP_HEADER_GAIN,
+            GainType::Album => bindings::OP_ALBUM_GAIN,
+            GainType::Track => bindings::OP_TRACK_GAIN,
+            GainType::Absolute => bindings::OP_ABSOLUTE_GAIN,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct OggOpusFile {
+    handle: *mut bindings::OggOpusFile,
+    // Backing store for a source installed through [`OggOpusFile::open_callbacks`].
+    // libopusfile takes ownership of the boxed source itself (the close
+    // trampoline reclaims it during `op_free`); this box merely keeps the
+    // callbacks table alive alongside the handle. `None` for the file, memory,
+    // and URL openers, which let libopusfile manage their own I/O.
+    callbacks: Option<Box<bindings::OpusFileCallbacks>>,
+}
 
 impl OggOpusFile {
     pub fn open_file(path: impl AsRef<Path>) -> Result<Self, OpusFileError> {
@@ -64,7 +108,10 @@ impl OggOpusFile {
         if handle.is_null() || error < 0 {
             Err(OpusFileError::from_i32(error).unwrap_or(OpusFileError::Unknown))
         } else {
-            Ok(Self(handle))
+            Ok(Self {
+                handle,
+                callbacks: None,
+            })
         }
     }
 
@@ -74,7 +121,10 @@ impl OggOpusFile {
         if handle.is_null() || error < 0 {
             Err(OpusFileError::from_i32(error).unwrap_or(OpusFileError::Unknown))
         } else {
-            Ok(Self(handle))
+            Ok(Self {
+                handle,
+                callbacks: None,
+            })
         }
     }
 
@@ -85,7 +135,41 @@ impl OggOpusFile {
         if handle.is_null() || error < 0 {
             Err(OpusFileError::from_i32(error).unwrap_or(OpusFileError::Unknown))
         } else {
-            Ok(Self(handle))
+            Ok(Self {
+                handle,
+                callbacks: None,
+            })
+        }
+    }
+
+    /// Open an Ogg Opus stream backed by any Rust [`Read`] `+` [`Seek`] source.
+    ///
+    /// The `source` is boxed onto the heap and handed to libopusfile behind a
+    /// stable pointer together with a table of `extern "C"` trampolines that
+    /// forward `read`/`seek`/`tell` onto it. libopusfile owns the boxed source
+    /// for the lifetime of the handle and reclaims it through the close
+    /// trampoline when the file is dropped, so the source need not be seekable
+    /// on the caller's side beyond the [`Seek`] bound.
+    pub fn open_callbacks<S: Read + Seek + 'static>(source: S) -> Result<Self, OpusFileError> {
+        let stream = Box::into_raw(Box::new(source)) as *mut c_void;
+        let callbacks = Box::new(bindings::OpusFileCallbacks {
+            read: Some(read_trampoline::<S>),
+            seek: Some(seek_trampoline::<S>),
+            tell: Some(tell_trampoline::<S>),
+            close: Some(close_trampoline::<S>),
+        });
+        let mut error = 0;
+        let handle = unsafe { op_open_callbacks(stream, &*callbacks, null(), 0, &mut error) };
+        if handle.is_null() || error < 0 {
+            // libopusfile does not invoke the close callback on a failed open,
+            // so reclaim the boxed source ourselves to avoid leaking it.
+            drop(unsafe { Box::from_raw(stream as *mut S) });
+            Err(OpusFileError::from_i32(error).unwrap_or(OpusFileError::Unknown))
+        } else {
+            Ok(Self {
+                handle,
+                callbacks: Some(callbacks),
+            })
         }
     }
 
@@ -96,7 +180,10 @@ impl OggOpusFile {
         if handle.is_null() || error < 0 {
             Err(OpusFileError::from_i32(error).unwrap_or(OpusFileError::Unknown))
         } else {
-            Ok(Self(handle))
+            Ok(Self {
+                handle,
+                callbacks: None,
+            })
         }
     }
 
@@ -106,7 +193,10 @@ impl OggOpusFile {
         if handle.is_null() || error < 0 {
             Err(OpusFileError::from_i32(error).unwrap_or(OpusFileError::Unknown))
         } else {
-            Ok(Self(handle))
+            Ok(Self {
+                handle,
+                callbacks: None,
+            })
         }
     }
 
@@ -117,12 +207,15 @@ impl OggOpusFile {
         if handle.is_null() || error < 0 {
             Err(OpusFileError::from_i32(error).unwrap_or(OpusFileError::Unknown))
         } else {
-            Ok(Self(handle))
+            Ok(Self {
+                handle,
+                callbacks: None,
+            })
         }
     }
 
     pub fn test_open(self) -> Result<Self, OpusFileError> {
-        let result = unsafe { op_test_open(self.0) };
+        let result = unsafe { op_test_open(self.handle) };
         if result < 0 {
             Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -131,23 +224,23 @@ impl OggOpusFile {
     }
 
     pub fn seekable(&self) -> bool {
-        unsafe { op_seekable(self.0) != 0 }
+        unsafe { op_seekable(self.handle) != 0 }
     }
 
     pub fn link_count(&self) -> usize {
-        unsafe { op_link_count(self.0) as usize }
+        unsafe { op_link_count(self.handle) as usize }
     }
 
     pub fn serial_number_of_link(&self, link_index: i32) -> u32 {
-        unsafe { op_serialno(self.0, link_index) }
+        unsafe { op_serialno(self.handle, link_index) }
     }
 
     pub fn channel_count(&self, link_index: i32) -> usize {
-        unsafe { op_channel_count(self.0, link_index) as usize }
+        unsafe { op_channel_count(self.handle, link_index) as usize }
     }
 
     pub fn raw_total(&self, link_index: i32) -> Result<usize, OpusFileError> {
-        let result = unsafe { op_raw_total(self.0, link_index) };
+        let result = unsafe { op_raw_total(self.handle, link_index) };
         if result < 0 {
             Err(OpusFileError::from_i64(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -156,7 +249,7 @@ impl OggOpusFile {
     }
 
     pub fn pcm_total(&self, link_index: i32) -> Result<usize, OpusFileError> {
-        let result = unsafe { op_pcm_total(self.0, link_index) };
+        let result = unsafe { op_pcm_total(self.handle, link_index) };
         if result < 0 {
             Err(OpusFileError::from_i64(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -164,26 +257,26 @@ impl OggOpusFile {
         }
     }
 
-    pub fn head(&self, link_index: i32) -> Result<OpusHead, OpusFileError> {
-        let result = unsafe { op_head(self.0, link_index) };
+    pub fn head(&self, link_index: i32) -> Result<OpusHead<'_>, OpusFileError> {
+        let result = unsafe { op_head(self.handle, link_index) };
         if result.is_null() {
             Err(OpusFileError::Unknown)
         } else {
-            Ok(OpusHead(result))
+            Ok(OpusHead(result, PhantomData))
         }
     }
 
-    pub fn tags(&self, link_index: i32) -> Result<OpusTags, OpusFileError> {
-        let result = unsafe { op_tags(self.0, link_index) };
+    pub fn tags(&self, link_index: i32) -> Result<OpusTags<'_>, OpusFileError> {
+        let result = unsafe { op_tags(self.handle, link_index) };
         if result.is_null() {
             Err(OpusFileError::Unknown)
         } else {
-            Ok(OpusTags(result))
+            Ok(OpusTags(result, PhantomData))
         }
     }
 
     pub fn current_link(&self) -> Result<i32, OpusFileError> {
-        let result = unsafe { op_current_link(self.0) };
+        let result = unsafe { op_current_link(self.handle) };
         if result < 0 {
             Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -192,7 +285,7 @@ impl OggOpusFile {
     }
 
     pub fn bitrate(&self, link_index: i32) -> Result<i32, OpusFileError> {
-        let result = unsafe { op_bitrate(self.0, link_index) };
+        let result = unsafe { op_bitrate(self.handle, link_index) };
         if result < 0 {
             Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -201,7 +294,7 @@ impl OggOpusFile {
     }
 
     pub fn bitrate_instant(&self) -> Result<i32, OpusFileError> {
-        let result = unsafe { op_bitrate_instant(self.0) };
+        let result = unsafe { op_bitrate_instant(self.handle) };
         if result < 0 {
             Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -210,7 +303,7 @@ impl OggOpusFile {
     }
 
     pub fn raw_tell(&self) -> Result<i64, OpusFileError> {
-        let result = unsafe { op_raw_tell(self.0) };
+        let result = unsafe { op_raw_tell(self.handle) };
         if result < 0 {
             Err(OpusFileError::from_i64(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -219,7 +312,7 @@ impl OggOpusFile {
     }
 
     pub fn pcm_tell(&self) -> Result<i64, OpusFileError> {
-        let result = unsafe { op_pcm_tell(self.0) };
+        let result = unsafe { op_pcm_tell(self.handle) };
         if result < 0 {
             Err(OpusFileError::from_i64(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -237,7 +330,7 @@ impl OggOpusFile {
             .unwrap_or(null_mut());
         let result = unsafe {
             op_read(
-                self.0,
+                self.handle,
                 buffer.as_mut_ptr(),
                 buffer.len().try_into().unwrap(),
                 link_index,
@@ -260,7 +353,7 @@ impl OggOpusFile {
             .unwrap_or(null_mut());
         let result = unsafe {
             op_read_float(
-                self.0,
+                self.handle,
                 buffer.as_mut_ptr(),
                 buffer.len().try_into().unwrap(),
                 link_index,
@@ -276,7 +369,7 @@ impl OggOpusFile {
     pub fn read_stereo(&self, buffer: &mut [i16]) -> Result<usize, OpusFileError> {
         let result = unsafe {
             op_read_stereo(
-                self.0,
+                self.handle,
                 buffer.as_mut_ptr(),
                 buffer.len().try_into().unwrap(),
             )
@@ -291,7 +384,7 @@ impl OggOpusFile {
     pub fn read_float_stereo(&self, buffer: &mut [f32]) -> Result<usize, OpusFileError> {
         let result = unsafe {
             op_read_float_stereo(
-                self.0,
+                self.handle,
                 buffer.as_mut_ptr(),
                 buffer.len().try_into().unwrap(),
             )
@@ -304,7 +397,7 @@ impl OggOpusFile {
     }
 
     pub fn raw_seek(&self, byte_offset: i64) -> Result<(), OpusFileError> {
-        let result = unsafe { op_raw_seek(self.0, byte_offset) };
+        let result = unsafe { op_raw_seek(self.handle, byte_offset) };
         if result < 0 {
             Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown))
         } else {
@@ -313,29 +406,472 @@ impl OggOpusFile {
     }
 
     pub fn pcm_seek(&self, sample_offset: i64) -> Result<(), OpusFileError> {
-        let result = unsafe { op_pcm_seek(self.0, sample_offset) };
+        let result = unsafe { op_pcm_seek(self.handle, sample_offset) };
         if result < 0 {
             Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown))
         } else {
             Ok(())
         }
     }
+
+    /// Set the gain applied to all subsequent [`read`](Self::read)/[`read_float`](Self::read_float)
+    /// calls.
+    ///
+    /// `offset_q8` is a gain offset in Q8 decibels (256 units per dB), added on
+    /// top of whichever gains `gain_type` selects. [`GainType::Absolute`]
+    /// disables the header gain and applies the offset alone.
+    pub fn set_gain_offset(
+        &self,
+        gain_type: GainType,
+        offset_q8: i32,
+    ) -> Result<(), OpusFileError> {
+        let result =
+            unsafe { op_set_gain_offset_q8(self.handle, gain_type.as_raw(), offset_q8) };
+        if result < 0 {
+            Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Honor the `R128_TRACK_GAIN` tag of `link_index`, returning whether it was present.
+    ///
+    /// Selects [`GainType::Track`] so libopusfile reads and applies the tag
+    /// itself; the offset is left at `0`.
+    pub fn apply_track_gain(&self, link_index: i32) -> Result<bool, OpusFileError> {
+        if self.tags(link_index)?.track_gain_db().is_none() {
+            return Ok(false);
+        }
+        self.set_gain_offset(GainType::Track, 0)?;
+        Ok(true)
+    }
+
+    /// Honor the `R128_ALBUM_GAIN` tag of `link_index`, returning whether it was present.
+    ///
+    /// Selects [`GainType::Album`] so libopusfile reads and applies the tag
+    /// itself; the offset is left at `0`.
+    pub fn apply_album_gain(&self, link_index: i32) -> Result<bool, OpusFileError> {
+        if self.tags(link_index)?.album_gain_db().is_none() {
+            return Ok(false);
+        }
+        self.set_gain_offset(GainType::Album, 0)?;
+        Ok(true)
+    }
+
+    /// Stream the file one decoded packet at a time as interleaved `f32` samples.
+    ///
+    /// Each step yields the samples of a single packet, transparently retrying
+    /// after an [`OpusFileError::Hole`] while surfacing every other error. The
+    /// yielded slice borrows the decoder's own buffer, so this is a lending
+    /// iterator exposing [`SamplesF32::next`] rather than [`Iterator`].
+    pub fn samples_f32(&self) -> SamplesF32 {
+        SamplesF32 {
+            file: self,
+            buffer: vec![0.0; MAX_FRAME_SAMPLES * MAX_CHANNELS],
+        }
+    }
+
+    /// Stream the file one decoded packet at a time as interleaved `i16` samples.
+    ///
+    /// See [`samples_f32`](Self::samples_f32) for the retry and borrowing behavior.
+    pub fn samples_i16(&self) -> SamplesI16 {
+        SamplesI16 {
+            file: self,
+            buffer: vec![0; MAX_FRAME_SAMPLES * MAX_CHANNELS],
+        }
+    }
+
+    /// Seek to the start and decode the entire seekable stream into interleaved `f32`.
+    pub fn decode_all_f32(&self) -> Result<DecodedAudio<f32>, OpusFileError> {
+        self.pcm_seek(0)?;
+        let mut samples = Vec::new();
+        let mut packets = self.samples_f32();
+        while let Some(packet) = packets.next() {
+            samples.extend_from_slice(packet?);
+        }
+        Ok(DecodedAudio {
+            samples,
+            channels: self.channel_count(0),
+            sample_rate: SAMPLE_RATE,
+        })
+    }
+
+    /// Seek to the start and decode the entire seekable stream into interleaved `i16`.
+    pub fn decode_all_i16(&self) -> Result<DecodedAudio<i16>, OpusFileError> {
+        self.pcm_seek(0)?;
+        let mut samples = Vec::new();
+        let mut packets = self.samples_i16();
+        while let Some(packet) = packets.next() {
+            samples.extend_from_slice(packet?);
+        }
+        Ok(DecodedAudio {
+            samples,
+            channels: self.channel_count(0),
+            sample_rate: SAMPLE_RATE,
+        })
+    }
+
+    /// The duration of `link_index` in seconds, derived from its total sample count.
+    pub fn duration_seconds(&self, link_index: i32) -> Result<f64, OpusFileError> {
+        Ok(self.pcm_total(link_index)? as f64 / SAMPLE_RATE as f64)
+    }
 }
 
 impl Drop for OggOpusFile {
     fn drop(&mut self) {
-        unsafe { op_free(self.0) }
+        // `op_free` runs the close trampoline, which reclaims the boxed source
+        // for callback-backed handles, before the callbacks table box is freed.
+        unsafe { op_free(self.handle) }
+    }
+}
+
+// The C `whence` values from `<stdio.h>`, which is blocklisted in the generated
+// bindings. They are part of the platform ABI and fixed across the platforms
+// libopusfile supports.
+const SEEK_SET: c_int = 0;
+const SEEK_CUR: c_int = 1;
+const SEEK_END: c_int = 2;
+
+/// Recover the boxed source from the opaque `stream` pointer and read into the
+/// C buffer, returning the number of bytes read or a negative value on error.
+unsafe extern "C" fn read_trampoline<S: Read + Seek>(
+    stream: *mut c_void,
+    ptr: *mut c_uchar,
+    nbytes: c_int,
+) -> c_int {
+    if nbytes < 0 {
+        return -1;
+    }
+    let source = &mut *(stream as *mut S);
+    let buffer = slice::from_raw_parts_mut(ptr, nbytes as usize);
+    match source.read(buffer) {
+        Ok(read) => read as c_int,
+        Err(_) => -1,
+    }
+}
+
+/// Map the C `whence` onto [`SeekFrom`] and seek the boxed source, returning 0
+/// on success or -1 on error.
+unsafe extern "C" fn seek_trampoline<S: Read + Seek>(
+    stream: *mut c_void,
+    offset: i64,
+    whence: c_int,
+) -> c_int {
+    let position = match whence {
+        SEEK_SET => SeekFrom::Start(offset as u64),
+        SEEK_CUR => SeekFrom::Current(offset),
+        SEEK_END => SeekFrom::End(offset),
+        _ => return -1,
+    };
+    let source = &mut *(stream as *mut S);
+    match source.seek(position) {
+        Ok(_) => 0,
+        Err(_) => -1,
+    }
+}
+
+/// Report the boxed source's current position, or -1 on error.
+unsafe extern "C" fn tell_trampoline<S: Read + Seek>(stream: *mut c_void) -> i64 {
+    let source = &mut *(stream as *mut S);
+    match source.stream_position() {
+        Ok(position) => position as i64,
+        Err(_) => -1,
+    }
+}
+
+/// Drop the boxed source. libopusfile calls this exactly once, from `op_free`.
+unsafe extern "C" fn close_trampoline<S>(stream: *mut c_void) -> c_int {
+    drop(Box::from_raw(stream as *mut S));
+    0
+}
+
+pub struct OpusHead<'a>(
+    pub *const bindings::OpusHead,
+    PhantomData<&'a OggOpusFile>,
+);
+
+impl<'a> OpusHead<'a> {
+    pub fn version(&self) -> i32 {
+        unsafe { (*self.0).version }
+    }
+
+    pub fn channel_count(&self) -> usize {
+        unsafe { (*self.0).channel_count as usize }
+    }
+
+    pub fn pre_skip(&self) -> u32 {
+        unsafe { (*self.0).pre_skip }
+    }
+
+    pub fn input_sample_rate(&self) -> u32 {
+        unsafe { (*self.0).input_sample_rate }
+    }
+
+    /// The output gain to apply to the decoded signal, in Q8 dB (256 units per dB).
+    pub fn output_gain(&self) -> i32 {
+        unsafe { (*self.0).output_gain }
+    }
+
+    pub fn mapping_family(&self) -> i32 {
+        unsafe { (*self.0).mapping_family }
+    }
+
+    pub fn stream_count(&self) -> usize {
+        unsafe { (*self.0).stream_count as usize }
+    }
+
+    pub fn coupled_count(&self) -> usize {
+        unsafe { (*self.0).coupled_count as usize }
+    }
+
+    /// The channel mapping table, one entry per output channel.
+    pub fn mapping(&self) -> &'a [u8] {
+        let count = self.channel_count();
+        unsafe { &(*self.0).mapping[..count] }
+    }
+}
+
+pub struct OpusTags<'a>(
+    pub *const bindings::OpusTags,
+    PhantomData<&'a OggOpusFile>,
+);
+
+impl<'a> OpusTags<'a> {
+    pub fn vendor(&self) -> Option<&'a str> {
+        let vendor = unsafe { (*self.0).vendor };
+        if vendor.is_null() {
+            None
+        } else {
+            unsafe { CStr::from_ptr(vendor) }.to_str().ok()
+        }
+    }
+
+    pub fn comment_count(&self) -> usize {
+        unsafe { (*self.0).comments }.max(0) as usize
+    }
+
+    /// The raw `TAG=VALUE` entry at `index`, or `None` if out of range or not valid UTF-8.
+    pub fn comment(&self, index: usize) -> Option<&'a str> {
+        if index >= self.comment_count() {
+            return None;
+        }
+        unsafe {
+            let comment = *(*self.0).user_comments.add(index);
+            let length = *(*self.0).comment_lengths.add(index);
+            if comment.is_null() || length < 0 {
+                return None;
+            }
+            from_utf8(slice::from_raw_parts(comment as *const u8, length as usize)).ok()
+        }
+    }
+
+    /// Iterate over the `TAG=VALUE` comment entries.
+    pub fn comments(&self) -> impl Iterator<Item = &'a str> + '_ {
+        (0..self.comment_count()).filter_map(|index| self.comment(index))
+    }
+
+    /// All values whose field name case-insensitively matches `key`.
+    pub fn get(&self, key: &str) -> Vec<&'a str> {
+        let key = match CString::new(key) {
+            Ok(key) => key,
+            Err(_) => return Vec::new(),
+        };
+        let count = self.query_count_cstr(&key);
+        let mut values = Vec::with_capacity(count);
+        for index in 0..count as c_int {
+            let value = unsafe { opus_tags_query(self.0, key.as_ptr(), index) };
+            if !value.is_null() {
+                if let Ok(value) = unsafe { CStr::from_ptr(value) }.to_str() {
+                    values.push(value);
+                }
+            }
+        }
+        values
+    }
+
+    /// The number of values whose field name case-insensitively matches `key`.
+    pub fn query_count(&self, key: &str) -> usize {
+        match CString::new(key) {
+            Ok(key) => self.query_count_cstr(&key),
+            Err(_) => 0,
+        }
+    }
+
+    fn query_count_cstr(&self, key: &CStr) -> usize {
+        unsafe { opus_tags_query_count(self.0, key.as_ptr()) }.max(0) as usize
+    }
+
+    /// The `R128_TRACK_GAIN` tag parsed into dB, if present.
+    pub fn track_gain_db(&self) -> Option<f32> {
+        let mut gain_q8 = 0;
+        let result = unsafe { opus_tags_get_track_gain(self.0, &mut gain_q8) };
+        (result == 0).then(|| gain_q8 as f32 / 256.0)
+    }
+
+    /// The `R128_ALBUM_GAIN` tag parsed into dB, if present.
+    pub fn album_gain_db(&self) -> Option<f32> {
+        let mut gain_q8 = 0;
+        let result = unsafe { opus_tags_get_album_gain(self.0, &mut gain_q8) };
+        (result == 0).then(|| gain_q8 as f32 / 256.0)
+    }
+
+    /// Decode every `METADATA_BLOCK_PICTURE` attachment, skipping unparseable ones.
+    pub fn pictures(&self) -> Vec<PictureTag> {
+        self.get("METADATA_BLOCK_PICTURE")
+            .into_iter()
+            .filter_map(|value| PictureTag::parse(value).ok())
+            .collect()
+    }
+
+    /// Parse a standalone `OpusTags` comment header packet into an owned value.
+    ///
+    /// Unlike [`OggOpusFile::tags`], which borrows the tags libopusfile already
+    /// parsed, this drives `opus_tags_parse` over a raw packet body (e.g. one
+    /// extracted from a container) and owns the resulting storage. Borrow it
+    /// with [`OwnedOpusTags::as_tags`] to reach the accessors above.
+    pub fn parse(packet: &[u8]) -> Result<OwnedOpusTags, OpusFileError> {
+        let mut tags = Box::new(unsafe { zeroed::<bindings::OpusTags>() });
+        let result = unsafe { opus_tags_parse(&mut *tags, packet.as_ptr(), packet.len()) };
+        if result < 0 {
+            // opus_tags_parse clears its own allocations on failure.
+            return Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown));
+        }
+        Ok(OwnedOpusTags(tags))
+    }
+}
+
+/// An [`OpusTags`] parsed from a raw comment packet, owning its backing storage.
+pub struct OwnedOpusTags(Box<bindings::OpusTags>);
+
+impl OwnedOpusTags {
+    /// Borrow the parsed tags, exposing the same accessors as [`OggOpusFile::tags`].
+    pub fn as_tags(&self) -> OpusTags<'_> {
+        OpusTags(&*self.0, PhantomData)
+    }
+}
+
+impl Drop for OwnedOpusTags {
+    fn drop(&mut self) {
+        unsafe { opus_tags_clear(&mut *self.0) };
     }
 }
 
-pub struct OpusHead(pub *const bindings::OpusHead);
+/// A decoded `METADATA_BLOCK_PICTURE` attachment.
+#[derive(Clone, Debug)]
+pub struct PictureTag {
+    pub picture_type: u32,
+    pub mime_type: String,
+    pub description: String,
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub colors: u32,
+    pub format: i32,
+    pub data: Vec<u8>,
+}
 
-impl OpusHead {
-    // TODO
+impl PictureTag {
+    /// Decode the base64 contents of a `METADATA_BLOCK_PICTURE` tag value.
+    pub fn parse(tag: &str) -> Result<Self, OpusFileError> {
+        let tag = CString::new(tag).map_err(|_| OpusFileError::InvalidParameters)?;
+        let mut picture = unsafe { zeroed::<bindings::OpusPictureTag>() };
+        unsafe { opus_picture_tag_init(&mut picture) };
+        let result = unsafe { opus_picture_tag_parse(&mut picture, tag.as_ptr()) };
+        if result < 0 {
+            unsafe { opus_picture_tag_clear(&mut picture) };
+            return Err(OpusFileError::from_i32(result).unwrap_or(OpusFileError::Unknown));
+        }
+        let parsed = Self {
+            picture_type: picture.type_ as u32,
+            mime_type: unsafe { owned_string(picture.mime_type) },
+            description: unsafe { owned_string(picture.description) },
+            width: picture.width,
+            height: picture.height,
+            depth: picture.depth,
+            colors: picture.colors,
+            format: picture.format,
+            data: if picture.data.is_null() {
+                Vec::new()
+            } else {
+                unsafe { slice::from_raw_parts(picture.data, picture.data_length as usize) }.to_vec()
+            },
+        };
+        unsafe { opus_picture_tag_clear(&mut picture) };
+        Ok(parsed)
+    }
 }
 
-pub struct OpusTags(pub *const bindings::OpusTags);
+/// Copy a (possibly null) C string into an owned [`String`], lossily for invalid UTF-8.
+unsafe fn owned_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
+/// Opus always decodes to 48 kHz, regardless of the original input sample rate.
+pub const SAMPLE_RATE: u32 = 48_000;
+
+// The largest packet a decoder can produce: 120 ms at 48 kHz, for up to eight
+// channels. Sizes the per-packet buffer so a full frame always fits.
+const MAX_FRAME_SAMPLES: usize = 5760;
+const MAX_CHANNELS: usize = 8;
+
+/// Fully decoded, interleaved PCM returned by the `decode_all_*` helpers.
+#[derive(Clone, Debug)]
+pub struct DecodedAudio<T> {
+    pub samples: Vec<T>,
+    pub channels: usize,
+    pub sample_rate: u32,
+}
+
+/// A lending iterator over decoded `f32` packets; see [`OggOpusFile::samples_f32`].
+pub struct SamplesF32<'a> {
+    file: &'a OggOpusFile,
+    buffer: Vec<f32>,
+}
 
-impl OpusTags {
-    // TODO
+impl SamplesF32<'_> {
+    /// Decode and return the next packet's interleaved samples, or `None` at end of stream.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Result<&[f32], OpusFileError>> {
+        loop {
+            match self.file.read_float(&mut self.buffer, None) {
+                Ok(0) => return None,
+                Ok(samples) => {
+                    let link = self.file.current_link().unwrap_or(0);
+                    let channels = self.file.channel_count(link);
+                    return Some(Ok(&self.buffer[..samples * channels]));
+                }
+                Err(OpusFileError::Hole) => continue,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+/// A lending iterator over decoded `i16` packets; see [`OggOpusFile::samples_i16`].
+pub struct SamplesI16<'a> {
+    file: &'a OggOpusFile,
+    buffer: Vec<i16>,
+}
+
+impl SamplesI16<'_> {
+    /// Decode and return the next packet's interleaved samples, or `None` at end of stream.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Result<&[i16], OpusFileError>> {
+        loop {
+            match self.file.read(&mut self.buffer, None) {
+                Ok(0) => return None,
+                Ok(samples) => {
+                    let link = self.file.current_link().unwrap_or(0);
+                    let channels = self.file.channel_count(link);
+                    return Some(Ok(&self.buffer[..samples * channels]));
+                }
+                Err(OpusFileError::Hole) => continue,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
 }